@@ -0,0 +1,144 @@
+//! `eth_watch` module tracks on-chain state relevant to the zkSync contract
+//! and answers [`EthWatchRequest`]s about it — in particular, whether a
+//! smart-contract wallet's `EIP1271` signature or a `ChangePubKey`
+//! authorization is valid on-chain.
+
+// Built-in uses
+// External uses
+use futures::{channel::oneshot, StreamExt};
+// Workspace uses
+use zksync_types::tx::EIP1271Signature;
+use zksync_types::{Address, Nonce, PubKeyHash};
+
+/// Requests answerable by the eth watch actor.
+#[derive(Debug)]
+pub enum EthWatchRequest {
+    IsPubkeyChangeAuthorized {
+        address: Address,
+        nonce: Nonce,
+        pubkey_hash: PubKeyHash,
+        resp: oneshot::Sender<bool>,
+    },
+    CheckEIP1271Signature {
+        address: Address,
+        message: Vec<u8>,
+        signature: EIP1271Signature,
+        resp: oneshot::Sender<Result<bool, anyhow::Error>>,
+    },
+    /// Batched form of `CheckEIP1271Signature`: carries a burst of checks
+    /// submitted by [`crate::signature_checker::on_chain_check_batcher`] in
+    /// one request, answered by
+    /// [`OnChainAuthorizationChecker::batch_check_eip1271_signatures`]; its
+    /// default implementation still makes one on-chain call per item.
+    ///
+    /// Results are returned in the same order as `items`.
+    BatchCheckEIP1271Signatures {
+        items: Vec<(Address, Vec<u8>, Vec<u8>)>,
+        resp: oneshot::Sender<Vec<Result<bool, anyhow::Error>>>,
+    },
+    /// Batched form of `IsPubkeyChangeAuthorized`.
+    ///
+    /// Results are returned in the same order as `items`.
+    BatchIsPubkeyChangeAuthorized {
+        items: Vec<(Address, Nonce, PubKeyHash)>,
+        resp: oneshot::Sender<Vec<bool>>,
+    },
+}
+
+/// Abstracts the on-chain calls `eth_watch` needs to answer [`EthWatchRequest`]s,
+/// so the actor's request-dispatch loop can be exercised independently of any
+/// concrete Ethereum client.
+#[async_trait::async_trait]
+pub trait OnChainAuthorizationChecker: Send + Sync {
+    async fn is_eip1271_signature_valid(
+        &self,
+        address: Address,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, anyhow::Error>;
+
+    async fn is_pubkey_change_authorized(
+        &self,
+        address: Address,
+        nonce: Nonce,
+        pubkey_hash: &PubKeyHash,
+    ) -> Result<bool, anyhow::Error>;
+
+    /// Services a batch of `EIP1271` checks with, ideally, a single
+    /// multicall. The default implementation falls back to one on-chain call
+    /// per item, which is always correct but doesn't save any round-trips;
+    /// a real Ethereum client implementation should override it.
+    async fn batch_check_eip1271_signatures(
+        &self,
+        items: &[(Address, Vec<u8>, Vec<u8>)],
+    ) -> Vec<Result<bool, anyhow::Error>> {
+        let mut results = Vec::with_capacity(items.len());
+        for (address, message, signature) in items {
+            results.push(
+                self.is_eip1271_signature_valid(*address, message, signature)
+                    .await,
+            );
+        }
+        results
+    }
+
+    /// Services a batch of pubkey-change authorization checks. See
+    /// [`Self::batch_check_eip1271_signatures`] for the fallback rationale.
+    async fn batch_is_pubkey_change_authorized(
+        &self,
+        items: &[(Address, Nonce, PubKeyHash)],
+    ) -> Vec<bool> {
+        let mut results = Vec::with_capacity(items.len());
+        for (address, nonce, pubkey_hash) in items {
+            let is_authorized = self
+                .is_pubkey_change_authorized(*address, *nonce, pubkey_hash)
+                .await
+                .unwrap_or(false);
+            results.push(is_authorized);
+        }
+        results
+    }
+}
+
+/// Main routine of the eth watch actor: answers each incoming
+/// [`EthWatchRequest`] using `checker`, until the request channel closes.
+pub async fn run_eth_watch<C: OnChainAuthorizationChecker>(
+    mut requests: futures::channel::mpsc::Receiver<EthWatchRequest>,
+    checker: C,
+) {
+    while let Some(request) = requests.next().await {
+        match request {
+            EthWatchRequest::IsPubkeyChangeAuthorized {
+                address,
+                nonce,
+                pubkey_hash,
+                resp,
+            } => {
+                let is_authorized = checker
+                    .is_pubkey_change_authorized(address, nonce, &pubkey_hash)
+                    .await
+                    .unwrap_or(false);
+                resp.send(is_authorized).unwrap_or_default();
+            }
+            EthWatchRequest::CheckEIP1271Signature {
+                address,
+                message,
+                signature,
+                resp,
+            } => {
+                let result = checker
+                    .is_eip1271_signature_valid(address, &message, &signature.0)
+                    .await;
+                resp.send(result).unwrap_or_default();
+            }
+            EthWatchRequest::BatchCheckEIP1271Signatures { items, resp } => {
+                let results = checker.batch_check_eip1271_signatures(&items).await;
+                resp.send(results).unwrap_or_default();
+            }
+            EthWatchRequest::BatchIsPubkeyChangeAuthorized { items, resp } => {
+                let results = checker.batch_is_pubkey_change_authorized(&items).await;
+                resp.send(results).unwrap_or_default();
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! `mempool` module exposes the error type returned to a client when a
+//! transaction fails one of the mempool's admission checks, whether that
+//! check ran locally (signature recovery, correctness) or required a
+//! round-trip to `eth_watch` (`ChangePubKey` authorization, `EIP1271`).
+
+use thiserror::Error;
+
+/// Reasons a transaction can be rejected before being admitted to the mempool.
+#[derive(Debug, Error, PartialEq)]
+pub enum TxAddError {
+    #[error("Ethereum signature is incorrect")]
+    IncorrectEthSignature,
+    #[error("Transaction is incorrect")]
+    IncorrectTx,
+    #[error("Change pubkey Ethereum authorization data is not valid")]
+    ChangePkNotAuthorized,
+    #[error("Failed to verify EIP1271 signature")]
+    EIP1271SignatureVerificationFail,
+    /// Returned immediately, without attempting the check, when the
+    /// signature checker's pending queue is already at capacity.
+    #[error("Too many pending signature checks, please try again later")]
+    TooManyPendingSignatureChecks,
+    /// Returned when every worker in a [`crate::signature_checker::RemoteVerifierPool`]
+    /// failed or timed out for a given request.
+    #[error("Signature verifier is currently unavailable")]
+    SignatureVerifierUnavailable,
+}
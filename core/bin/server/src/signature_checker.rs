@@ -3,21 +3,347 @@
 //! Main routine of this module operates a multithreaded event loop,
 //! which is used to spawn concurrent tasks to efficiently check the
 //! transactions signatures.
+//!
+//! Verification itself is pluggable behind the [`SignatureVerifier`] trait:
+//! by default it happens in-process, but [`start_sign_checker_detached_relayer`]
+//! can instead fan requests out to a pool of remote verifier workers.
 
+// Built-in uses
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 // External uses
+use async_trait::async_trait;
 use futures::{
     channel::{mpsc, oneshot},
     SinkExt, StreamExt,
 };
+use lru::LruCache;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, Secp256k1};
+use tiny_keccak::{Hasher, Keccak};
 use tokio::runtime::{Builder, Handle};
+use tokio::sync::Semaphore;
 // Workspace uses
-use zksync_types::{tx::TxEthSignature, SignedZkSyncTx, ZkSyncTx};
+use zksync_types::{
+    tx::TxEthSignature, Address, BigUint, Nonce, PubKeyHash, SignedZkSyncTx, Transfer, Withdraw,
+    ZkSyncTx,
+};
 // Local uses
 use crate::eth_watch::EthWatchRequest;
 use crate::mempool::TxAddError;
 use crate::panic_notify::ThreadPanicNotify;
 use zksync_types::tx::EthSignData;
 
+/// How many verification outcomes each of the verification caches keeps around.
+/// Sized generously since entries are cheap (a key and a unit value).
+const VERIFICATION_CACHE_CAPACITY: usize = 100_000;
+
+/// Maximum number of individual on-chain checks folded into one batched
+/// `eth_watch` request before the batch is dispatched early.
+const ON_CHAIN_CHECK_BATCH_MAX_SIZE: usize = 50;
+/// Maximum time an on-chain check waits for its batch to fill up before the
+/// batch is dispatched regardless of size.
+const ON_CHAIN_CHECK_BATCH_WINDOW: Duration = Duration::from_millis(10);
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Left-pads `bytes` to 32 bytes (big-endian), or `None` if `bytes` is
+/// already longer than 32 bytes and can't be represented as a `uint256`/
+/// `address` word at all.
+fn left_pad_32(bytes: &[u8]) -> Option<[u8; 32]> {
+    if bytes.len() > 32 {
+        return None;
+    }
+
+    let mut padded = [0u8; 32];
+    let start = padded.len() - bytes.len();
+    padded[start..].copy_from_slice(bytes);
+    Some(padded)
+}
+
+/// Encodes `value` as an EIP-712 `uint256` word, or `None` if it doesn't fit
+/// in 256 bits. Unlike the other `left_pad_32` call sites in this file,
+/// `value` comes from transaction fields an attacker controls directly, so
+/// this can't be an `unwrap`/`expect`.
+fn biguint_to_u256_bytes(value: &BigUint) -> Option<[u8; 32]> {
+    left_pad_32(&value.to_bytes_be())
+}
+
+/// EIP-712 domain parameters for typed-data signatures, sourced from the
+/// node configuration so that a signature produced for one network (or one
+/// instance of the zkSync contract) can't be replayed on another.
+#[derive(Debug, Clone, Copy)]
+pub struct Eip712Domain {
+    pub chain_id: u64,
+    pub verifying_contract: Address,
+}
+
+const EIP712_DOMAIN_NAME: &str = "zkSync";
+const EIP712_DOMAIN_VERSION: &str = "1";
+const EIP712_DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const EIP712_TRANSFER_TYPE: &[u8] = b"Transfer(uint32 accountId,address from,address to,uint16 token,uint256 amount,uint256 fee,uint32 nonce,uint256 validFrom,uint256 validUntil)";
+const EIP712_WITHDRAW_TYPE: &[u8] = b"Withdraw(uint32 accountId,address from,address to,uint16 token,uint256 amount,uint256 fee,uint32 nonce,uint256 validFrom,uint256 validUntil)";
+
+fn eip712_domain_separator(domain: &Eip712Domain) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(32 * 4);
+    encoded.extend_from_slice(&keccak256(EIP712_DOMAIN_TYPE));
+    encoded.extend_from_slice(&keccak256(EIP712_DOMAIN_NAME.as_bytes()));
+    encoded.extend_from_slice(&keccak256(EIP712_DOMAIN_VERSION.as_bytes()));
+    encoded.extend_from_slice(
+        &left_pad_32(&domain.chain_id.to_be_bytes()).expect("u64 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(
+        &left_pad_32(domain.verifying_contract.as_bytes())
+            .expect("an Address is always 20 bytes, which always fits in an address word"),
+    );
+
+    keccak256(&encoded)
+}
+
+/// Hashes `tx` per `EIP712_TRANSFER_TYPE`, or `None` if `tx.amount`/`tx.fee`
+/// is too large to encode as a `uint256` (these come straight from the
+/// wire, so a crafted transaction can't be assumed to fit).
+fn hash_struct_transfer(tx: &Transfer) -> Option<[u8; 32]> {
+    let time_range = tx.time_range.unwrap_or_default();
+
+    let mut encoded = Vec::with_capacity(32 * 10);
+    encoded.extend_from_slice(&keccak256(EIP712_TRANSFER_TYPE));
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.account_id.0.to_be_bytes()).expect("u32 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(&left_pad_32(tx.from.as_bytes()).expect("Address is 20 bytes"));
+    encoded.extend_from_slice(&left_pad_32(tx.to.as_bytes()).expect("Address is 20 bytes"));
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.token.0.to_be_bytes()).expect("u16 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(&biguint_to_u256_bytes(&tx.amount)?);
+    encoded.extend_from_slice(&biguint_to_u256_bytes(&tx.fee)?);
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.nonce.0.to_be_bytes()).expect("u32 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(
+        &left_pad_32(&time_range.valid_from.to_be_bytes())
+            .expect("u64 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(
+        &left_pad_32(&time_range.valid_until.to_be_bytes())
+            .expect("u64 always fits in a uint256 word"),
+    );
+
+    Some(keccak256(&encoded))
+}
+
+/// Hashes `tx` per `EIP712_WITHDRAW_TYPE`. See [`hash_struct_transfer`] for
+/// why this returns `Option`.
+fn hash_struct_withdraw(tx: &Withdraw) -> Option<[u8; 32]> {
+    let time_range = tx.time_range.unwrap_or_default();
+
+    let mut encoded = Vec::with_capacity(32 * 10);
+    encoded.extend_from_slice(&keccak256(EIP712_WITHDRAW_TYPE));
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.account_id.0.to_be_bytes()).expect("u32 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(&left_pad_32(tx.from.as_bytes()).expect("Address is 20 bytes"));
+    encoded.extend_from_slice(&left_pad_32(tx.to.as_bytes()).expect("Address is 20 bytes"));
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.token.0.to_be_bytes()).expect("u16 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(&biguint_to_u256_bytes(&tx.amount)?);
+    encoded.extend_from_slice(&biguint_to_u256_bytes(&tx.fee)?);
+    encoded.extend_from_slice(
+        &left_pad_32(&tx.nonce.0.to_be_bytes()).expect("u32 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(
+        &left_pad_32(&time_range.valid_from.to_be_bytes())
+            .expect("u64 always fits in a uint256 word"),
+    );
+    encoded.extend_from_slice(
+        &left_pad_32(&time_range.valid_until.to_be_bytes())
+            .expect("u64 always fits in a uint256 word"),
+    );
+
+    Some(keccak256(&encoded))
+}
+
+/// Computes the EIP-712 digest (`keccak256(0x1901 || domainSeparator ||
+/// hashStruct(message))`) for the given transaction, or `None` if EIP-712
+/// signing isn't supported for this transaction kind, or if one of its
+/// fields doesn't fit the `uint256` encoding EIP-712 requires.
+fn eip712_digest(tx: &ZkSyncTx, domain: &Eip712Domain) -> Option<[u8; 32]> {
+    let hash_struct = match tx {
+        ZkSyncTx::Transfer(transfer) => hash_struct_transfer(transfer)?,
+        ZkSyncTx::Withdraw(withdraw) => hash_struct_withdraw(withdraw)?,
+        _ => return None,
+    };
+
+    let domain_separator = eip712_domain_separator(domain);
+
+    let mut preimage = Vec::with_capacity(2 + 32 + 32);
+    preimage.extend_from_slice(&[0x19, 0x01]);
+    preimage.extend_from_slice(&domain_separator);
+    preimage.extend_from_slice(&hash_struct);
+
+    Some(keccak256(&preimage))
+}
+
+/// Recovers the Ethereum address that produced `signature` over `digest`.
+///
+/// Unlike `PackedEthSignature::signature_recover_signer`, this does *not*
+/// apply the `"\x19Ethereum Signed Message:\n" || len || message` personal-sign
+/// prefix before hashing — `digest` here is already a final hash (e.g. an
+/// EIP-712 digest), and re-wrapping it in that prefix would recover the
+/// signer of a completely different message, breaking every valid signature.
+fn ecrecover_raw(digest: &[u8; 32], signature: &[u8]) -> Result<Address, TxAddError> {
+    if signature.len() != 65 {
+        return Err(TxAddError::IncorrectEthSignature);
+    }
+
+    let recovery_byte = signature[64];
+    let recovery_id = RecoveryId::from_i32(if recovery_byte >= 27 {
+        i32::from(recovery_byte - 27)
+    } else {
+        i32::from(recovery_byte)
+    })
+    .map_err(|_| TxAddError::IncorrectEthSignature)?;
+
+    let recoverable_signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)
+        .map_err(|_| TxAddError::IncorrectEthSignature)?;
+    let message = Message::from_slice(digest).map_err(|_| TxAddError::IncorrectEthSignature)?;
+
+    let public_key = Secp256k1::new()
+        .recover(&message, &recoverable_signature)
+        .map_err(|_| TxAddError::IncorrectEthSignature)?;
+
+    // Ethereum addresses are the last 20 bytes of keccak256 of the
+    // uncompressed public key, excluding its leading 0x04 tag byte.
+    let public_key_bytes = public_key.serialize_uncompressed();
+    let hash = keccak256(&public_key_bytes[1..]);
+    Ok(Address::from_slice(&hash[12..]))
+}
+
+/// Caches the outcome of successful, but expensive, signature and
+/// authorization checks so that repeated requests (e.g. the same contract
+/// wallet signing many transactions in a batch) don't have to pay for another
+/// `signature_recover_signer` call or `eth_watch` round-trip.
+///
+/// Only successful outcomes are cached: a cache miss always falls back to
+/// doing the real check, so a miss can only make verification slower, never
+/// reject a valid transaction.
+///
+/// Shared between worker tasks behind an `Arc`; each individual cache is
+/// guarded by its own `Mutex` so unrelated checks don't contend with each other.
+#[derive(Default)]
+struct VerificationCache {
+    /// Keyed on `keccak256(address || keccak256(message) || signature_bytes)`.
+    ///
+    /// The expected signer address is folded into the key (not just the
+    /// message and signature) so that a cached hit for `(message, sig)`
+    /// can't be replayed to authorize a different claimed account: without
+    /// it, caching the outcome of a successful recovery for account A would
+    /// also make the identical `(message, sig)` pair appear "verified" for
+    /// any other account B that happens to submit a tx referencing the same
+    /// signature bytes.
+    ecdsa: Mutex<LruCache<[u8; 32], ()>>,
+    /// Keyed on `(address, message_hash, signature)`.
+    eip1271: Mutex<LruCache<(Address, [u8; 32], Vec<u8>), ()>>,
+    /// Keyed on `(account, nonce, pubkey_hash)`.
+    pubkey_change_authorized: Mutex<LruCache<(Address, Nonce, PubKeyHash), ()>>,
+    /// Highest `ChangePubKey` nonce seen per account, used to invalidate
+    /// stale `pubkey_change_authorized` entries.
+    max_nonce_seen: Mutex<HashMap<Address, Nonce>>,
+}
+
+impl VerificationCache {
+    fn new() -> Self {
+        Self {
+            ecdsa: Mutex::new(LruCache::new(VERIFICATION_CACHE_CAPACITY)),
+            eip1271: Mutex::new(LruCache::new(VERIFICATION_CACHE_CAPACITY)),
+            pubkey_change_authorized: Mutex::new(LruCache::new(VERIFICATION_CACHE_CAPACITY)),
+            max_nonce_seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn ecdsa_key(signer: Address, message: &[u8], packed_signature: &[u8]) -> [u8; 32] {
+        let message_hash = keccak256(message);
+        let mut preimage = Vec::with_capacity(
+            signer.as_bytes().len() + message_hash.len() + packed_signature.len(),
+        );
+        preimage.extend_from_slice(signer.as_bytes());
+        preimage.extend_from_slice(&message_hash);
+        preimage.extend_from_slice(packed_signature);
+        keccak256(&preimage)
+    }
+
+    fn is_ecdsa_verified(&self, signer: Address, message: &[u8], packed_signature: &[u8]) -> bool {
+        let key = Self::ecdsa_key(signer, message, packed_signature);
+        self.ecdsa.lock().unwrap().get(&key).is_some()
+    }
+
+    fn cache_ecdsa_verified(&self, signer: Address, message: &[u8], packed_signature: &[u8]) {
+        let key = Self::ecdsa_key(signer, message, packed_signature);
+        self.ecdsa.lock().unwrap().put(key, ());
+    }
+
+    fn is_eip1271_verified(&self, address: Address, message: &[u8], signature: &[u8]) -> bool {
+        let key = (address, keccak256(message), signature.to_vec());
+        self.eip1271.lock().unwrap().get(&key).is_some()
+    }
+
+    fn cache_eip1271_verified(&self, address: Address, message: &[u8], signature: &[u8]) {
+        let key = (address, keccak256(message), signature.to_vec());
+        self.eip1271.lock().unwrap().put(key, ());
+    }
+
+    /// Returns `true` if authorization of `(account, nonce, pubkey_hash)` is
+    /// known to be granted and hasn't been made stale by a higher nonce seen
+    /// for the account since.
+    fn is_pubkey_change_authorized(
+        &self,
+        account: Address,
+        nonce: Nonce,
+        pubkey_hash: &PubKeyHash,
+    ) -> bool {
+        if let Some(&max_nonce) = self.max_nonce_seen.lock().unwrap().get(&account) {
+            if nonce < max_nonce {
+                return false;
+            }
+        }
+
+        let key = (account, nonce, pubkey_hash.clone());
+        self.pubkey_change_authorized
+            .lock()
+            .unwrap()
+            .get(&key)
+            .is_some()
+    }
+
+    fn cache_pubkey_change_authorized(
+        &self,
+        account: Address,
+        nonce: Nonce,
+        pubkey_hash: &PubKeyHash,
+    ) {
+        let mut max_nonce_seen = self.max_nonce_seen.lock().unwrap();
+        let max_nonce = max_nonce_seen.entry(account).or_insert(nonce);
+        if nonce > *max_nonce {
+            *max_nonce = nonce;
+        }
+        drop(max_nonce_seen);
+
+        let key = (account, nonce, pubkey_hash.clone());
+        self.pubkey_change_authorized.lock().unwrap().put(key, ());
+    }
+}
+
 /// Wrapper on a `ZkSyncTx` which guarantees that
 /// transaction was checked and signatures associated with
 /// this transactions are correct.
@@ -32,9 +358,11 @@ impl VerifiedTx {
     /// Ethereum signature (if required) and `ZKSync` signature.
     pub async fn verify(
         request: &VerifyTxSignatureRequest,
-        eth_watch_req: mpsc::Sender<EthWatchRequest>,
+        on_chain_check_req: mpsc::Sender<BatchableCheck>,
+        cache: Arc<VerificationCache>,
+        eip712_domain: Eip712Domain,
     ) -> Result<Self, TxAddError> {
-        verify_eth_signature(&request, eth_watch_req)
+        verify_eth_signature(&request, on_chain_check_req, cache, eip712_domain)
             .await
             .and_then(|_| verify_tx_correctness(request.tx.clone()))
             .map(|tx| {
@@ -54,30 +382,63 @@ impl VerifiedTx {
     pub fn inner(&self) -> &SignedZkSyncTx {
         &self.0
     }
+
+    /// Wraps `tx` as verified without performing any check itself.
+    ///
+    /// # Contract
+    /// Unlike [`VerifiedTx::verify`], this does not check anything — it
+    /// exists solely so a [`VerifierTransport`] implementation can hand back
+    /// a `VerifiedTx` for a transaction that *another* process already ran
+    /// through `VerifiedTx::verify` (e.g. a remote verifier worker). Callers
+    /// must not construct a `VerifiedTx` this way for a transaction that
+    /// hasn't actually been verified somewhere.
+    pub fn assume_verified(tx: SignedZkSyncTx) -> Self {
+        Self(tx)
+    }
 }
 
 /// Verifies the Ethereum signature of the transaction.
 async fn verify_eth_signature(
     request: &VerifyTxSignatureRequest,
-    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    mut on_chain_check_req: mpsc::Sender<BatchableCheck>,
+    cache: Arc<VerificationCache>,
+    eip712_domain: Eip712Domain,
 ) -> Result<(), TxAddError> {
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
     if let ZkSyncTx::ChangePubKey(change_pk) = &request.tx {
         if change_pk.eth_signature.is_none() {
-            // Check that user is allowed to perform this operation.
-            let eth_watch_resp = oneshot::channel();
-            eth_watch_req
-                .clone()
-                .send(EthWatchRequest::IsPubkeyChangeAuthorized {
-                    address: change_pk.account,
-                    nonce: change_pk.nonce,
-                    pubkey_hash: change_pk.new_pk_hash.clone(),
-                    resp: eth_watch_resp.0,
-                })
-                .await
-                .expect("ETH watch req receiver dropped");
+            let is_authorized = if cache.is_pubkey_change_authorized(
+                change_pk.account,
+                change_pk.nonce,
+                &change_pk.new_pk_hash,
+            ) {
+                true
+            } else {
+                // Check that user is allowed to perform this operation. The
+                // check is folded into a batch with other pending
+                // authorization checks before it is sent to `eth_watch`.
+                let resp = oneshot::channel();
+                on_chain_check_req
+                    .send(BatchableCheck::PubkeyChangeAuthorized {
+                        address: change_pk.account,
+                        nonce: change_pk.nonce,
+                        pubkey_hash: change_pk.new_pk_hash.clone(),
+                        resp: resp.0,
+                    })
+                    .await
+                    .expect("On-chain check batcher dropped");
+
+                let is_authorized = resp.1.await.expect("Err response from eth watch batcher");
+                if is_authorized {
+                    cache.cache_pubkey_change_authorized(
+                        change_pk.account,
+                        change_pk.nonce,
+                        &change_pk.new_pk_hash,
+                    );
+                }
+                is_authorized
+            };
 
-            let is_authorized = eth_watch_resp.1.await.expect("Err response from eth watch");
             if !is_authorized {
                 return Err(TxAddError::ChangePkNotAuthorized);
             }
@@ -88,13 +449,21 @@ async fn verify_eth_signature(
     if let Some(sign_data) = &request.eth_sign_data {
         match &sign_data.signature {
             TxEthSignature::EthereumSignature(packed_signature) => {
+                let message = sign_data.message.as_bytes();
+                let expected_account = request.tx.account();
+                if cache.is_ecdsa_verified(expected_account, message, &packed_signature.0) {
+                    return Ok(());
+                }
+
                 let signer_account = packed_signature
-                    .signature_recover_signer(&sign_data.message.as_bytes())
+                    .signature_recover_signer(message)
                     .or(Err(TxAddError::IncorrectEthSignature))?;
 
-                if signer_account != request.tx.account() {
+                if signer_account != expected_account {
                     return Err(TxAddError::IncorrectEthSignature);
                 }
+
+                cache.cache_ecdsa_verified(expected_account, message, &packed_signature.0);
             }
             TxEthSignature::EIP1271Signature(signature) => {
                 let message = format!(
@@ -102,29 +471,55 @@ async fn verify_eth_signature(
                     sign_data.message.len(),
                     &sign_data.message
                 );
+                let message = message.into_bytes();
+                let address = request.tx.account();
 
-                let eth_watch_resp = oneshot::channel();
-                eth_watch_req
-                    .clone()
-                    .send(EthWatchRequest::CheckEIP1271Signature {
-                        address: request.tx.account(),
-                        message: message.into_bytes(),
-                        signature: signature.clone(),
-                        resp: eth_watch_resp.0,
+                if cache.is_eip1271_verified(address, &message, &signature.0) {
+                    return Ok(());
+                }
+
+                // Folded into a batch with other pending EIP-1271 checks
+                // before it is sent to `eth_watch` as one batched request.
+                let resp = oneshot::channel();
+                on_chain_check_req
+                    .send(BatchableCheck::Eip1271Signature {
+                        address,
+                        message: message.clone(),
+                        signature: signature.0.clone(),
+                        resp: resp.0,
                     })
                     .await
-                    .expect("ETH watch req receiver dropped");
+                    .expect("On-chain check batcher dropped");
 
-                let signature_correct = eth_watch_resp
+                let signature_correct = resp
                     .1
                     .await
-                    .expect("Failed receiving response from eth watch")
+                    .expect("Failed receiving response from eth watch batcher")
                     .map_err(|e| log::warn!("Err in eth watch: {}", e))
                     .or(Err(TxAddError::EIP1271SignatureVerificationFail))?;
 
                 if !signature_correct {
                     return Err(TxAddError::IncorrectTx);
                 }
+
+                cache.cache_eip1271_verified(address, &message, &signature.0);
+            }
+            TxEthSignature::Eip712Signature(packed_signature) => {
+                let digest = eip712_digest(&request.tx, &eip712_domain)
+                    .ok_or(TxAddError::IncorrectEthSignature)?;
+                let expected_account = request.tx.account();
+
+                if cache.is_ecdsa_verified(expected_account, &digest, &packed_signature.0) {
+                    return Ok(());
+                }
+
+                let signer_account = ecrecover_raw(&digest, &packed_signature.0)?;
+
+                if signer_account != expected_account {
+                    return Err(TxAddError::IncorrectEthSignature);
+                }
+
+                cache.cache_ecdsa_verified(expected_account, &digest, &packed_signature.0);
             }
         };
     }
@@ -132,6 +527,189 @@ async fn verify_eth_signature(
     Ok(())
 }
 
+/// A single on-chain check submitted by a worker task, waiting to be folded
+/// into one batched `eth_watch` request by [`on_chain_check_batcher`].
+#[derive(Debug)]
+pub enum BatchableCheck {
+    Eip1271Signature {
+        address: Address,
+        message: Vec<u8>,
+        signature: Vec<u8>,
+        resp: oneshot::Sender<Result<bool, anyhow::Error>>,
+    },
+    PubkeyChangeAuthorized {
+        address: Address,
+        nonce: Nonce,
+        pubkey_hash: PubKeyHash,
+        resp: oneshot::Sender<bool>,
+    },
+}
+
+/// Accumulates individual `EIP1271Signature`/pubkey-authorization checks
+/// submitted by worker tasks and periodically folds each kind into a single
+/// batched `EthWatchRequest`, so `eth_watch` can service a burst of such
+/// checks with one multicall instead of N serialized round-trips.
+///
+/// A batch is dispatched once it reaches [`ON_CHAIN_CHECK_BATCH_MAX_SIZE`]
+/// items or [`ON_CHAIN_CHECK_BATCH_WINDOW`] has elapsed since its first item
+/// was queued, whichever comes first.
+async fn on_chain_check_batcher(
+    mut checks: mpsc::Receiver<BatchableCheck>,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+) {
+    let mut eip1271_batch = Vec::new();
+    let mut pubkey_auth_batch = Vec::new();
+
+    // Armed once, when the first item of a new batch is queued, and pinned
+    // across loop iterations so it keeps counting down from that moment
+    // instead of being restarted every time another item arrives — otherwise
+    // steady sub-threshold traffic would keep pushing the deadline out and
+    // a batch could wait arbitrarily long before being flushed.
+    let deadline = tokio::time::delay_for(ON_CHAIN_CHECK_BATCH_WINDOW);
+    tokio::pin!(deadline);
+    let mut deadline_armed = false;
+
+    loop {
+        tokio::select! {
+            check = checks.next() => {
+                match check {
+                    Some(BatchableCheck::Eip1271Signature { address, message, signature, resp }) => {
+                        if eip1271_batch.is_empty() && pubkey_auth_batch.is_empty() {
+                            deadline.as_mut().reset(tokio::time::Instant::now() + ON_CHAIN_CHECK_BATCH_WINDOW);
+                            deadline_armed = true;
+                        }
+                        eip1271_batch.push((address, message, signature, resp));
+                        if eip1271_batch.len() >= ON_CHAIN_CHECK_BATCH_MAX_SIZE {
+                            flush_eip1271_batch(&mut eip1271_batch, &eth_watch_req).await;
+                        }
+                    }
+                    Some(BatchableCheck::PubkeyChangeAuthorized { address, nonce, pubkey_hash, resp }) => {
+                        if eip1271_batch.is_empty() && pubkey_auth_batch.is_empty() {
+                            deadline.as_mut().reset(tokio::time::Instant::now() + ON_CHAIN_CHECK_BATCH_WINDOW);
+                            deadline_armed = true;
+                        }
+                        pubkey_auth_batch.push((address, nonce, pubkey_hash, resp));
+                        if pubkey_auth_batch.len() >= ON_CHAIN_CHECK_BATCH_MAX_SIZE {
+                            flush_pubkey_auth_batch(&mut pubkey_auth_batch, &eth_watch_req).await;
+                        }
+                    }
+                    None => {
+                        flush_eip1271_batch(&mut eip1271_batch, &eth_watch_req).await;
+                        flush_pubkey_auth_batch(&mut pubkey_auth_batch, &eth_watch_req).await;
+                        return;
+                    }
+                }
+            }
+            _ = &mut deadline, if deadline_armed => {
+                flush_eip1271_batch(&mut eip1271_batch, &eth_watch_req).await;
+                flush_pubkey_auth_batch(&mut pubkey_auth_batch, &eth_watch_req).await;
+                deadline_armed = false;
+            }
+        }
+    }
+}
+
+type Eip1271BatchItem = (
+    Address,
+    Vec<u8>,
+    Vec<u8>,
+    oneshot::Sender<Result<bool, anyhow::Error>>,
+);
+
+async fn flush_eip1271_batch(
+    batch: &mut Vec<Eip1271BatchItem>,
+    eth_watch_req: &mpsc::Sender<EthWatchRequest>,
+) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let (items, responders): (Vec<_>, Vec<_>) = std::mem::take(batch)
+        .into_iter()
+        .map(|(address, message, signature, resp)| ((address, message, signature), resp))
+        .unzip();
+
+    let resp = oneshot::channel();
+    eth_watch_req
+        .clone()
+        .send(EthWatchRequest::BatchCheckEIP1271Signatures {
+            items,
+            resp: resp.0,
+        })
+        .await
+        .expect("ETH watch req receiver dropped");
+
+    let results = resp
+        .1
+        .await
+        .expect("Failed receiving response from eth watch");
+
+    if results.len() != responders.len() {
+        log::error!(
+            "eth_watch returned {} results for a batch of {} EIP-1271 checks; \
+             treating the missing ones as failed checks",
+            results.len(),
+            responders.len()
+        );
+    }
+
+    let mut results = results.into_iter();
+    for responder in responders {
+        let result = results.next().unwrap_or_else(|| {
+            Err(anyhow::anyhow!(
+                "eth_watch didn't return a result for this check"
+            ))
+        });
+        responder.send(result).unwrap_or_default();
+    }
+}
+
+type PubkeyAuthBatchItem = (Address, Nonce, PubKeyHash, oneshot::Sender<bool>);
+
+async fn flush_pubkey_auth_batch(
+    batch: &mut Vec<PubkeyAuthBatchItem>,
+    eth_watch_req: &mpsc::Sender<EthWatchRequest>,
+) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let (items, responders): (Vec<_>, Vec<_>) = std::mem::take(batch)
+        .into_iter()
+        .map(|(address, nonce, pubkey_hash, resp)| ((address, nonce, pubkey_hash), resp))
+        .unzip();
+
+    let resp = oneshot::channel();
+    eth_watch_req
+        .clone()
+        .send(EthWatchRequest::BatchIsPubkeyChangeAuthorized {
+            items,
+            resp: resp.0,
+        })
+        .await
+        .expect("ETH watch req receiver dropped");
+
+    let results = resp
+        .1
+        .await
+        .expect("Failed receiving response from eth watch");
+
+    if results.len() != responders.len() {
+        log::error!(
+            "eth_watch returned {} results for a batch of {} pubkey-authorization checks; \
+             treating the missing ones as not authorized",
+            results.len(),
+            responders.len()
+        );
+    }
+
+    let mut results = results.into_iter();
+    for responder in responders {
+        let result = results.next().unwrap_or(false);
+        responder.send(result).unwrap_or_default();
+    }
+}
+
 /// Verifies the correctness of the ZKSync transaction (including the
 /// signature check).
 fn verify_tx_correctness(mut tx: ZkSyncTx) -> Result<ZkSyncTx, TxAddError> {
@@ -154,28 +732,379 @@ pub struct VerifyTxSignatureRequest {
     pub response: oneshot::Sender<Result<VerifiedTx, TxAddError>>,
 }
 
+impl VerifyTxSignatureRequest {
+    /// Requests that only need a cheap, local ECDSA recovery are prioritized
+    /// over requests which require an on-chain round-trip (`EIP1271` and
+    /// authorization-less `ChangePubKey`), so that a burst of the latter
+    /// cannot starve the former out of the worker pool.
+    fn priority(&self) -> SigningRequestPriority {
+        if let ZkSyncTx::ChangePubKey(change_pk) = &self.tx {
+            if change_pk.eth_signature.is_none() {
+                return SigningRequestPriority::OnChainCheck;
+            }
+        }
+
+        match self.eth_sign_data.as_ref().map(|data| &data.signature) {
+            Some(TxEthSignature::EIP1271Signature(_)) => SigningRequestPriority::OnChainCheck,
+            _ => SigningRequestPriority::Recovery,
+        }
+    }
+}
+
+/// Relative priority of a pending signature check request.
+/// Lower variants are drained from the [`PendingSignatureQueue`] first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SigningRequestPriority {
+    /// Plain ECDSA recovery: cheap and fully local.
+    Recovery,
+    /// Requires an `eth_watch` round-trip (`EIP1271` or pubkey-change authorization).
+    OnChainCheck,
+}
+
+/// Bounded, priority-ordered queue of signature check requests awaiting a free
+/// worker slot. Requests that only require a local ECDSA recovery are served
+/// ahead of the ones that need an on-chain check, so expensive requests cannot
+/// delay cheap ones.
+///
+/// Once `capacity` pending requests are queued, further requests are rejected
+/// immediately with [`TxAddError::TooManyPendingSignatureChecks`] instead of
+/// growing the queue without bound.
+struct PendingSignatureQueue {
+    capacity: usize,
+    recovery: VecDeque<VerifyTxSignatureRequest>,
+    on_chain_check: VecDeque<VerifyTxSignatureRequest>,
+}
+
+impl PendingSignatureQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            recovery: VecDeque::new(),
+            on_chain_check: VecDeque::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.recovery.len() + self.on_chain_check.len()
+    }
+
+    /// Attempts to enqueue the request, responding with
+    /// `TooManyPendingSignatureChecks` right away if the queue is saturated.
+    fn push(&mut self, request: VerifyTxSignatureRequest) {
+        if self.len() >= self.capacity {
+            request
+                .response
+                .send(Err(TxAddError::TooManyPendingSignatureChecks))
+                .unwrap_or_default();
+            return;
+        }
+
+        match request.priority() {
+            SigningRequestPriority::Recovery => self.recovery.push_back(request),
+            SigningRequestPriority::OnChainCheck => self.on_chain_check.push_back(request),
+        }
+    }
+
+    fn pop(&mut self) -> Option<VerifyTxSignatureRequest> {
+        self.recovery
+            .pop_front()
+            .or_else(|| self.on_chain_check.pop_front())
+    }
+}
+
+/// Verifies a single signature check request, producing a [`VerifiedTx`] on
+/// success. The in-process implementation ([`InProcessVerifier`]) is the
+/// default; [`RemoteVerifierPool`] fans the same work out to remote worker
+/// processes instead, letting signature checking scale horizontally.
+///
+/// Besides [`VerifiedTx::verify`], the only other way to build a `VerifiedTx`
+/// is [`VerifiedTx::assume_verified`], which a [`VerifierTransport`] uses to
+/// wrap a result that a remote worker already verified. It performs no check
+/// of its own, so that path relies on its callers keeping its contract.
+#[async_trait]
+pub trait SignatureVerifier: Send + Sync {
+    async fn verify(&self, request: &VerifyTxSignatureRequest) -> Result<VerifiedTx, TxAddError>;
+}
+
+/// Default [`SignatureVerifier`]: performs the ECDSA recovery and `eth_watch`
+/// on-chain checks in-process, same as before this trait existed.
+struct InProcessVerifier {
+    on_chain_check_req: mpsc::Sender<BatchableCheck>,
+    cache: Arc<VerificationCache>,
+    eip712_domain: Eip712Domain,
+}
+
+#[async_trait]
+impl SignatureVerifier for InProcessVerifier {
+    async fn verify(&self, request: &VerifyTxSignatureRequest) -> Result<VerifiedTx, TxAddError> {
+        VerifiedTx::verify(
+            request,
+            self.on_chain_check_req.clone(),
+            Arc::clone(&self.cache),
+            self.eip712_domain,
+        )
+        .await
+    }
+}
+
+/// A remote signature-verification worker, addressed by some
+/// transport-specific identifier (e.g. a gRPC or HTTP URL).
+#[derive(Debug, Clone)]
+pub struct VerifierWorkerEndpoint {
+    pub address: String,
+}
+
+/// Failure modes specific to dispatching a check to a remote verifier
+/// worker, as opposed to the verification itself legitimately failing.
+#[derive(Debug)]
+pub enum RemoteVerifierError {
+    Timeout,
+    Transport(String),
+}
+
+/// Abstracts the actual network call to a remote verifier worker, so that
+/// [`RemoteVerifierPool`]'s routing and failover logic can be exercised
+/// independently of any concrete transport.
+///
+/// The worker on the other end of this call is expected to have produced its
+/// `VerifiedTx` by running the request through [`VerifiedTx::verify`] itself;
+/// an implementation wraps whatever came back over the wire into a
+/// `VerifiedTx` with [`VerifiedTx::assume_verified`], rather than being able
+/// to construct one directly.
+#[async_trait]
+pub trait VerifierTransport: Send + Sync {
+    async fn verify(
+        &self,
+        endpoint: &VerifierWorkerEndpoint,
+        request: &VerifyTxSignatureRequest,
+    ) -> Result<Result<VerifiedTx, TxAddError>, RemoteVerifierError>;
+}
+
+/// Number of consecutive failures (errors or timeouts) after which a worker
+/// is considered unhealthy and skipped by round-robin selection, until it
+/// succeeds again.
+const WORKER_UNHEALTHY_THRESHOLD: u32 = 3;
+
+struct RemoteWorkerState {
+    endpoint: VerifierWorkerEndpoint,
+    consecutive_failures: AtomicU32,
+}
+
+impl RemoteWorkerState {
+    fn is_healthy(&self) -> bool {
+        self.consecutive_failures.load(Ordering::Relaxed) < WORKER_UNHEALTHY_THRESHOLD
+    }
+}
+
+/// Relayer-mode [`SignatureVerifier`]: fans verification work out to a fixed
+/// set of remote worker endpoints, selecting between them round-robin and
+/// preferring workers that haven't recently failed. A timeout or transport
+/// error fails over to the next worker rather than failing the request
+/// outright, as long as some worker remains to try.
+pub struct RemoteVerifierPool<T: VerifierTransport> {
+    transport: T,
+    workers: Vec<RemoteWorkerState>,
+    next_worker: AtomicUsize,
+    request_timeout: Duration,
+}
+
+impl<T: VerifierTransport> RemoteVerifierPool<T> {
+    pub fn new(
+        transport: T,
+        endpoints: Vec<VerifierWorkerEndpoint>,
+        request_timeout: Duration,
+    ) -> Self {
+        assert!(
+            !endpoints.is_empty(),
+            "a remote verifier pool needs at least one worker endpoint"
+        );
+
+        let workers = endpoints
+            .into_iter()
+            .map(|endpoint| RemoteWorkerState {
+                endpoint,
+                consecutive_failures: AtomicU32::new(0),
+            })
+            .collect();
+
+        Self {
+            transport,
+            workers,
+            next_worker: AtomicUsize::new(0),
+            request_timeout,
+        }
+    }
+
+    /// Picks the `attempt`-th worker to try for a request that started its
+    /// round robin at `start`: the first attempt starts at `start` and skips
+    /// unhealthy workers; if every worker is unhealthy, round robin degrades
+    /// to trying them all anyway rather than refusing to serve requests.
+    fn pick_worker(&self, start: usize, attempt: usize) -> &RemoteWorkerState {
+        let len = self.workers.len();
+
+        // Scan at most `len` candidates from a single health snapshot — not
+        // an unbounded `while`. `is_healthy()` reads a `Relaxed` atomic
+        // that other requests' `verify()` calls update concurrently, so a
+        // worker this scan sees as healthy on one check can flip unhealthy
+        // by the next; looping on `is_healthy()` directly can spin forever
+        // if every worker flips unhealthy between the guard and the scan.
+        for offset in 0..len {
+            let idx = (start + attempt + offset) % len;
+            if self.workers[idx].is_healthy() {
+                return &self.workers[idx];
+            }
+        }
+
+        // Every worker is unhealthy (or flipped unhealthy mid-scan): fall
+        // back to plain round robin rather than refusing to serve requests.
+        &self.workers[(start + attempt) % len]
+    }
+}
+
+#[async_trait]
+impl<T: VerifierTransport> SignatureVerifier for RemoteVerifierPool<T> {
+    async fn verify(&self, request: &VerifyTxSignatureRequest) -> Result<VerifiedTx, TxAddError> {
+        // Advanced once per request (not once per attempt/retry within it),
+        // so consecutive requests fan out across workers instead of a
+        // failed first attempt handing the retry right back to the same
+        // worker it just failed over from.
+        let start = self.next_worker.fetch_add(1, Ordering::Relaxed);
+
+        for attempt in 0..self.workers.len() {
+            let worker = self.pick_worker(start, attempt);
+            let call = self.transport.verify(&worker.endpoint, request);
+
+            match tokio::time::timeout(self.request_timeout, call).await {
+                Ok(Ok(result)) => {
+                    worker.consecutive_failures.store(0, Ordering::Relaxed);
+                    return result;
+                }
+                Ok(Err(err)) => {
+                    log::warn!(
+                        "Remote signature verifier {} failed: {:?}",
+                        worker.endpoint.address,
+                        err
+                    );
+                    worker.consecutive_failures.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(_elapsed) => {
+                    log::warn!(
+                        "Remote signature verifier {} timed out",
+                        worker.endpoint.address
+                    );
+                    worker.consecutive_failures.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        Err(TxAddError::SignatureVerifierUnavailable)
+    }
+}
+
+/// Builds the [`SignatureVerifier`] the checker routine dispatches requests
+/// to. Takes the Tokio `Handle` so an implementation that needs a background
+/// task (like the in-process verifier's on-chain check batcher) can spawn it
+/// on the same runtime the workers run on.
+type VerifierFactory = Box<dyn FnOnce(&Handle) -> Arc<dyn SignatureVerifier> + Send>;
+
 /// Main routine of the concurrent signature checker.
 /// See the module documentation for details.
+///
+/// `max_parallel_checks` bounds the size of the worker pool (and thus the
+/// amount of concurrent CPU-bound ECDSA recoveries and in-flight `eth_watch`
+/// requests), while `max_pending_checks` bounds how many requests may wait
+/// for a free worker before the checker starts rejecting new ones with
+/// `TxAddError::TooManyPendingSignatureChecks`.
 pub fn start_sign_checker_detached(
     input: mpsc::Receiver<VerifyTxSignatureRequest>,
     eth_watch_req: mpsc::Sender<EthWatchRequest>,
     panic_notify: mpsc::Sender<bool>,
+    max_parallel_checks: usize,
+    max_pending_checks: usize,
+    eip712_domain: Eip712Domain,
+) {
+    let verifier_factory: VerifierFactory = Box::new(move |handle| {
+        let (on_chain_check_req, on_chain_check_resp) = mpsc::channel(max_parallel_checks);
+        handle.spawn(on_chain_check_batcher(on_chain_check_resp, eth_watch_req));
+
+        Arc::new(InProcessVerifier {
+            on_chain_check_req,
+            cache: Arc::new(VerificationCache::new()),
+            eip712_domain,
+        })
+    });
+
+    start_sign_checker_detached_with_verifier(
+        input,
+        panic_notify,
+        max_parallel_checks,
+        max_pending_checks,
+        verifier_factory,
+    );
+}
+
+/// Relayer mode: like [`start_sign_checker_detached`], but dispatches
+/// verification to `verifier` (typically a [`RemoteVerifierPool`]) instead
+/// of performing it in-process.
+pub fn start_sign_checker_detached_relayer(
+    input: mpsc::Receiver<VerifyTxSignatureRequest>,
+    panic_notify: mpsc::Sender<bool>,
+    max_parallel_checks: usize,
+    max_pending_checks: usize,
+    verifier: Arc<dyn SignatureVerifier>,
+) {
+    start_sign_checker_detached_with_verifier(
+        input,
+        panic_notify,
+        max_parallel_checks,
+        max_pending_checks,
+        Box::new(move |_handle| verifier),
+    );
+}
+
+fn start_sign_checker_detached_with_verifier(
+    input: mpsc::Receiver<VerifyTxSignatureRequest>,
+    panic_notify: mpsc::Sender<bool>,
+    max_parallel_checks: usize,
+    max_pending_checks: usize,
+    verifier_factory: VerifierFactory,
 ) {
     /// Main signature check requests handler.
-    /// Basically it receives the requests through the channel and verifies signatures,
-    /// notifying the request sender about the check result.
+    /// Receives the requests through the channel, queues them by priority and
+    /// dispatches them to a bounded pool of workers, notifying the request
+    /// sender about the check result.
     async fn checker_routine(
         handle: Handle,
         mut input: mpsc::Receiver<VerifyTxSignatureRequest>,
-        eth_watch_req: mpsc::Sender<EthWatchRequest>,
+        max_parallel_checks: usize,
+        max_pending_checks: usize,
+        verifier_factory: VerifierFactory,
     ) {
-        while let Some(request) = input.next().await {
-            let eth_watch_req = eth_watch_req.clone();
-            handle.spawn(async move {
-                let resp = VerifiedTx::verify(&request, eth_watch_req).await;
+        let verifier = verifier_factory(&handle);
+        let worker_slots = Arc::new(Semaphore::new(max_parallel_checks));
+        let mut pending = PendingSignatureQueue::new(max_pending_checks);
+        let mut input_closed = false;
+
+        while !input_closed || pending.len() > 0 {
+            tokio::select! {
+                request = input.next(), if !input_closed => {
+                    match request {
+                        Some(request) => pending.push(request),
+                        None => input_closed = true,
+                    }
+                }
+                permit = Arc::clone(&worker_slots).acquire_owned(), if pending.len() > 0 => {
+                    let permit = permit.expect("signature checker semaphore is never closed");
+                    let request = pending.pop().expect("queue was checked to be non-empty");
+                    let verifier = Arc::clone(&verifier);
+                    handle.spawn(async move {
+                        let _permit = permit;
+                        let resp = verifier.verify(&request).await;
 
-                request.response.send(resp).unwrap_or_default();
-            });
+                        request.response.send(resp).unwrap_or_default();
+                    });
+                }
+            }
         }
     }
 
@@ -190,7 +1119,287 @@ pub fn start_sign_checker_detached(
                 .build()
                 .expect("failed to build runtime for signature processor");
             let handle = runtime.handle().clone();
-            runtime.block_on(checker_routine(handle, input, eth_watch_req));
+            runtime.block_on(checker_routine(
+                handle,
+                input,
+                max_parallel_checks,
+                max_pending_checks,
+                verifier_factory,
+            ));
         })
         .expect("failed to start signature checker thread");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::ChangePubKey;
+
+    struct AlwaysFailTransport {
+        calls: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl VerifierTransport for AlwaysFailTransport {
+        async fn verify(
+            &self,
+            endpoint: &VerifierWorkerEndpoint,
+            _request: &VerifyTxSignatureRequest,
+        ) -> Result<Result<VerifiedTx, TxAddError>, RemoteVerifierError> {
+            self.calls.lock().unwrap().push(endpoint.address.clone());
+            Err(RemoteVerifierError::Transport("boom".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_verifier_pool_round_robins_once_per_request_not_per_attempt() {
+        let pool = RemoteVerifierPool::new(
+            AlwaysFailTransport {
+                calls: Mutex::new(Vec::new()),
+            },
+            vec![
+                VerifierWorkerEndpoint {
+                    address: "a".to_string(),
+                },
+                VerifierWorkerEndpoint {
+                    address: "b".to_string(),
+                },
+            ],
+            Duration::from_millis(50),
+        );
+
+        let (request, _recv) = recovery_priority_request();
+        assert!(pool.verify(&request).await.is_err());
+        let (request, _recv) = recovery_priority_request();
+        assert!(pool.verify(&request).await.is_err());
+
+        // Both workers always fail, so each request retries both of them —
+        // but the *starting* worker must differ between requests, otherwise
+        // the first attempt of every request lands on the worker the
+        // previous request just failed over from.
+        let calls = pool.transport.calls.lock().unwrap().clone();
+        assert_eq!(calls, vec!["a", "b", "b", "a"]);
+    }
+
+    #[test]
+    fn pick_worker_terminates_and_falls_back_to_round_robin_when_every_worker_is_unhealthy() {
+        let pool = RemoteVerifierPool::new(
+            AlwaysFailTransport {
+                calls: Mutex::new(Vec::new()),
+            },
+            vec![
+                VerifierWorkerEndpoint {
+                    address: "a".to_string(),
+                },
+                VerifierWorkerEndpoint {
+                    address: "b".to_string(),
+                },
+            ],
+            Duration::from_millis(50),
+        );
+
+        for worker in &pool.workers {
+            worker
+                .consecutive_failures
+                .store(WORKER_UNHEALTHY_THRESHOLD, Ordering::Relaxed);
+        }
+
+        // Must return rather than spin forever scanning for a healthy
+        // worker that doesn't exist.
+        assert_eq!(pool.pick_worker(0, 0).endpoint.address, "a");
+        assert_eq!(pool.pick_worker(0, 1).endpoint.address, "b");
+    }
+
+    #[test]
+    fn keccak256_matches_known_answer_vectors() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn eip712_domain_separator_matches_known_answer_vector() {
+        let domain = Eip712Domain {
+            chain_id: 1,
+            verifying_contract: Address::from_slice(&[0xcc; 20]),
+        };
+
+        assert_eq!(
+            hex::encode(eip712_domain_separator(&domain)),
+            "df4224d6860634dbb2816496d4884a6c0bb1692098d92c1bb11c0f045dd21d27"
+        );
+    }
+
+    #[test]
+    fn eip712_domain_separator_binds_the_chain_id() {
+        // A signature valid on one network must not verify on another: the
+        // domain separator (and so the final digest) has to change with it.
+        let contract = Address::from_slice(&[0xcc; 20]);
+        let mainnet = Eip712Domain {
+            chain_id: 1,
+            verifying_contract: contract,
+        };
+        let other_chain = Eip712Domain {
+            chain_id: 2,
+            verifying_contract: contract,
+        };
+
+        assert_ne!(
+            eip712_domain_separator(&mainnet),
+            eip712_domain_separator(&other_chain)
+        );
+    }
+
+    #[test]
+    fn eip712_digest_rejects_an_amount_that_does_not_fit_a_uint256_instead_of_panicking() {
+        let domain = Eip712Domain {
+            chain_id: 1,
+            verifying_contract: Address::from_slice(&[0xcc; 20]),
+        };
+        let oversized_amount = BigUint::from(1u8) << 256usize;
+
+        let mut transfer = Transfer::default();
+        transfer.amount = oversized_amount;
+        let tx = ZkSyncTx::Transfer(Box::new(transfer));
+
+        assert_eq!(eip712_digest(&tx, &domain), None);
+    }
+
+    #[tokio::test]
+    async fn flush_eip1271_batch_fills_in_missing_results_instead_of_dropping_responders() {
+        let (eth_watch_req, mut eth_watch_recv) = mpsc::channel(1);
+        let mut batch: Vec<Eip1271BatchItem> = Vec::new();
+        let mut receivers = Vec::new();
+        for _ in 0..3 {
+            let (resp, recv) = oneshot::channel();
+            batch.push((
+                Address::from_slice(&[1; 20]),
+                b"msg".to_vec(),
+                b"sig".to_vec(),
+                resp,
+            ));
+            receivers.push(recv);
+        }
+
+        let flush = flush_eip1271_batch(&mut batch, &eth_watch_req);
+        let serve_short_response = async {
+            match eth_watch_recv.next().await.unwrap() {
+                EthWatchRequest::BatchCheckEIP1271Signatures { items, resp } => {
+                    assert_eq!(items.len(), 3);
+                    // eth_watch returns fewer results than were requested.
+                    resp.send(vec![Ok(true)]).unwrap();
+                }
+                _ => panic!("unexpected eth watch request"),
+            }
+        };
+        futures::join!(flush, serve_short_response);
+
+        assert!(matches!(receivers.remove(0).await, Ok(Ok(true))));
+        // Responders past the short response still get a (failed) result
+        // instead of their oneshot sender being silently dropped.
+        assert!(receivers.remove(0).await.unwrap().is_err());
+        assert!(receivers.remove(0).await.unwrap().is_err());
+    }
+
+    #[test]
+    fn ecdsa_cache_does_not_let_one_account_replay_anothers_verified_signature() {
+        let cache = VerificationCache::new();
+        let message = b"some tx payload";
+        let signature = [7u8; 65];
+        let account_a = Address::from_slice(&[0xaa; 20]);
+        let account_b = Address::from_slice(&[0xbb; 20]);
+
+        assert!(!cache.is_ecdsa_verified(account_a, message, &signature));
+        cache.cache_ecdsa_verified(account_a, message, &signature);
+
+        assert!(cache.is_ecdsa_verified(account_a, message, &signature));
+        // The same (message, signature) pair must not be considered verified
+        // for a different claimed account.
+        assert!(!cache.is_ecdsa_verified(account_b, message, &signature));
+    }
+
+    #[test]
+    fn pubkey_change_authorization_is_invalidated_by_a_higher_nonce() {
+        let cache = VerificationCache::new();
+        let account = Address::from_slice(&[0xcc; 20]);
+        let pubkey_hash = PubKeyHash::default();
+
+        cache.cache_pubkey_change_authorized(account, Nonce(1), &pubkey_hash);
+        assert!(cache.is_pubkey_change_authorized(account, Nonce(1), &pubkey_hash));
+
+        // A later authorization at a higher nonce makes the earlier one stale.
+        cache.cache_pubkey_change_authorized(account, Nonce(2), &pubkey_hash);
+        assert!(!cache.is_pubkey_change_authorized(account, Nonce(1), &pubkey_hash));
+        assert!(cache.is_pubkey_change_authorized(account, Nonce(2), &pubkey_hash));
+    }
+
+    fn recovery_priority_request() -> (
+        VerifyTxSignatureRequest,
+        oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
+    ) {
+        let (response, receiver) = oneshot::channel();
+        let request = VerifyTxSignatureRequest {
+            tx: ZkSyncTx::Transfer(Box::new(Transfer::default())),
+            eth_sign_data: None,
+            response,
+        };
+        (request, receiver)
+    }
+
+    fn on_chain_check_priority_request() -> (
+        VerifyTxSignatureRequest,
+        oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
+    ) {
+        let (response, receiver) = oneshot::channel();
+        let request = VerifyTxSignatureRequest {
+            tx: ZkSyncTx::ChangePubKey(Box::new(ChangePubKey::default())),
+            eth_sign_data: None,
+            response,
+        };
+        (request, receiver)
+    }
+
+    #[test]
+    fn pending_queue_serves_recovery_before_on_chain_check() {
+        let mut queue = PendingSignatureQueue::new(10);
+        let (on_chain_check, _on_chain_check_recv) = on_chain_check_priority_request();
+        let (recovery, _recovery_recv) = recovery_priority_request();
+
+        // Enqueued in the "worse" order: the cheap recovery request arrives
+        // after the expensive on-chain check, but must still be popped first.
+        queue.push(on_chain_check);
+        queue.push(recovery);
+
+        let first = queue.pop().unwrap();
+        assert_eq!(first.priority(), SigningRequestPriority::Recovery);
+        let second = queue.pop().unwrap();
+        assert_eq!(second.priority(), SigningRequestPriority::OnChainCheck);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn pending_queue_rejects_once_saturated() {
+        let mut queue = PendingSignatureQueue::new(1);
+
+        let (first, _first_recv) = recovery_priority_request();
+        queue.push(first);
+        assert_eq!(queue.len(), 1);
+
+        let (second, second_recv) = recovery_priority_request();
+        queue.push(second);
+
+        // The queue was already full, so the second request is rejected
+        // immediately rather than being enqueued.
+        assert_eq!(queue.len(), 1);
+        let result = second_recv.try_recv().unwrap().unwrap();
+        assert!(matches!(
+            result,
+            Err(TxAddError::TooManyPendingSignatureChecks)
+        ));
+    }
+}